@@ -1,26 +1,36 @@
 use crate::common::{
-    deserialize_from_file, list_downloaded_pages, serialize_to_file, Article, GalnetError,
-    DOWNLOADED_PAGES_FILE, EXTRACTED_FILES_LOCATION, FAILED_PAGES_FILE,
+    deserialize_from_file, join_paragraphs, list_downloaded_pages, revert_galnet_date,
+    serialize_to_file, Article, ContentFormat, GalnetError, DOWNLOADED_PAGES_FILE,
+    EXTRACTED_FILES_LOCATION, FAILED_PAGES_FILE,
 };
 
 use chrono::naive::NaiveDateTime;
 use chrono::prelude::Utc;
-use futures::future::join_all;
+use rand::Rng;
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
     fmt::Debug,
-    fs, vec,
+    fs,
+    sync::Arc,
+    vec,
 };
+use tokio::sync::Semaphore;
+use tokio::time::Duration;
 
-use scraper::{ElementRef, Html, Selector};
+use scraper::{ElementRef, Html, Node, Selector};
 
 const ELITE_DANGEROUS_COMMUNITY_SITE: &str = "https://community.elitedangerous.com";
 
 // const EXTRACT_LOCATION: &str = "./galnet";
 
+/// Upper bound on the backoff delay between retries, regardless of `base_delay` and attempt count.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+/// Jitter added on top of the computed backoff, to avoid retries from a batch landing in lockstep.
+const RETRY_JITTER_MAX_MILLIS: u64 = 250;
+
 lazy_static! {
     // PARSING
     static ref GALNET_DATE_LINK_SELECTOR: Selector =
@@ -35,10 +45,6 @@ lazy_static! {
     static ref ARTICLE_CONTENT_SELECTOR: Selector =
         Selector::parse(":scope > p").expect("Article content selector");
     static ref URL_UID_MATCHER: Regex = Regex::new(r"/uid/([^/#?]+)").expect("URL UID matcher");
-
-    // MATCHERS
-    static ref ARTICLE_DATE_MATCHER: Regex =
-        Regex::new(r"(\d{2})[\s-](\w{3})[\s-](\d{4,})").expect("Article date matcher");
 }
 
 #[derive(Debug)]
@@ -49,18 +55,47 @@ struct PageExtraction {
     errors: Vec<GalnetError>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct ErroredPage {
     url: String,
     errors: Vec<String>,
 }
 
+fn list_failed_pages() -> Result<HashMap<String, ErroredPage>, Box<dyn Error>> {
+    let entries: Vec<(String, ErroredPage)> =
+        deserialize_from_file(&FAILED_PAGES_FILE)?.unwrap_or_default();
+    Ok(entries.into_iter().collect())
+}
+
 fn with_site_base_url(url: &str) -> String {
     ELITE_DANGEROUS_COMMUNITY_SITE.to_owned() + url
 }
 
-async fn fetch_text(link: &str) -> Result<String, Box<dyn Error>> {
-    Ok(reqwest::get(link).await?.text().await?)
+/// Fetches `link`, retrying transient failures with exponential backoff.
+///
+/// Waits `base_delay * 2^attempt` (capped at `RETRY_MAX_DELAY`, plus a small random jitter)
+/// between attempts, giving up after `max_attempts` tries and returning the last error.
+async fn fetch_text(link: &str, max_attempts: u32, base_delay: Duration) -> Result<String, Box<dyn Error>> {
+    let mut attempt = 0;
+    loop {
+        let result: Result<String, Box<dyn Error>> = async {
+            Ok(reqwest::get(link).await?.text().await?)
+        }
+        .await;
+
+        match result {
+            Ok(text) => return Ok(text),
+            Err(e) if attempt + 1 >= max_attempts => return Err(e),
+            Err(_) => {
+                let backoff = base_delay
+                    .saturating_mul(2u32.saturating_pow(attempt))
+                    .min(RETRY_MAX_DELAY);
+                let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..RETRY_JITTER_MAX_MILLIS));
+                tokio::time::sleep(backoff + jitter).await;
+                attempt += 1;
+            }
+        }
+    }
 }
 
 fn extract_date_links(html: &str) -> HashSet<String> {
@@ -71,14 +106,19 @@ fn extract_date_links(html: &str) -> HashSet<String> {
         .collect()
 }
 
-async fn extract_page(url: &str) -> PageExtraction {
+async fn extract_page(
+    url: &str,
+    max_attempts: u32,
+    base_delay: Duration,
+    content_format: ContentFormat,
+) -> PageExtraction {
     let mut articles = HashSet::new();
     let mut links = HashSet::new();
     let mut errors = vec![];
-    match fetch_text(url).await {
+    match fetch_text(url, max_attempts, base_delay).await {
         Ok(html) => {
             links = extract_date_links(&html);
-            extract_articles(&html)
+            extract_articles(&html, content_format)
                 .into_iter()
                 .for_each(|result| match result {
                     Ok(article) => {
@@ -112,7 +152,44 @@ async fn extract_page(url: &str) -> PageExtraction {
     }
 }
 
-fn extract_articles(html: &str) -> Vec<Result<Article, GalnetError>> {
+/// Collects an element's text, turning `<br>` children into line breaks instead of dropping them,
+/// so `get_paragraph_text` can split a paragraph's visual lines back out.
+fn get_element_text_with_breaks(element_ref: &ElementRef) -> String {
+    let mut text = String::new();
+    for child in element_ref.children() {
+        match child.value() {
+            Node::Text(chunk) => text.push_str(chunk),
+            Node::Element(element) if element.name() == "br" => text.push('\n'),
+            Node::Element(_) => {
+                if let Some(child_ref) = ElementRef::wrap(child) {
+                    text.push_str(&get_element_text_with_breaks(&child_ref));
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Extracts a `<p>`'s non-empty, trimmed lines, or `None` if it holds only whitespace.
+/// No other boilerplate is filtered out.
+fn get_paragraph_text(paragraph: &ElementRef) -> Option<String> {
+    let lines = get_element_text_with_breaks(paragraph)
+        .split('\n')
+        .map(|line| line.trim().to_owned())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn extract_articles(
+    html: &str,
+    content_format: ContentFormat,
+) -> Vec<Result<Article, GalnetError>> {
     let parser_error = |cause: &str| {
         Err(GalnetError::ParserError {
             cause: cause.into(),
@@ -165,11 +242,14 @@ fn extract_articles(html: &str) -> Vec<Result<Article, GalnetError>> {
                 return parser_error(&format!("Couldn't find article \"{}\" date", uid));
             };
 
-            let content = if let Some(content_el) = select_in_article(&ARTICLE_CONTENT_SELECTOR) {
-                get_element_text(&content_el)
-            } else {
+            let paragraphs = article
+                .select(&ARTICLE_CONTENT_SELECTOR)
+                .filter_map(|paragraph| get_paragraph_text(&paragraph))
+                .collect::<Vec<_>>();
+            if paragraphs.is_empty() {
                 return parser_error(&format!("Couldn't find article \"{}\" content", uid));
-            };
+            }
+            let content = join_paragraphs(&paragraphs, content_format);
 
             Ok(Article {
                 uid,
@@ -188,7 +268,12 @@ fn extract_articles(html: &str) -> Vec<Result<Article, GalnetError>> {
         .collect()
 }
 
-async fn extract_page_to_file(url: &str) -> PageExtraction {
+async fn extract_page_to_file(
+    url: &str,
+    max_attempts: u32,
+    base_delay: Duration,
+    content_format: ContentFormat,
+) -> PageExtraction {
     let gen_article_filename = |article: &Article| -> String {
         format!(
             "{}/{} - {} - {}.json",
@@ -198,7 +283,7 @@ async fn extract_page_to_file(url: &str) -> PageExtraction {
             article.uid
         )
     };
-    let mut page_extraction = extract_page(url).await;
+    let mut page_extraction = extract_page(url, max_attempts, base_delay, content_format).await;
 
     for article in &page_extraction.articles {
         let filename = gen_article_filename(article);
@@ -228,10 +313,24 @@ async fn extract_page_to_file(url: &str) -> PageExtraction {
     page_extraction
 }
 
-pub async fn extract_all_pages(sequentially: bool) -> Result<(), Box<dyn Error>> {
-    let html = fetch_text(ELITE_DANGEROUS_COMMUNITY_SITE).await?;
-
-    let mut failed_pages = HashMap::new();
+/// Downloads every GalNet date page not yet in `DOWNLOADED_PAGES_FILE`, running at most
+/// `workers` fetches concurrently. Each fetch is tried up to `retry_max_attempts` times in all,
+/// backing off by `retry_base_delay * 2^attempt` between tries.
+pub async fn extract_all_pages(
+    workers: usize,
+    retry_base_delay: Duration,
+    retry_max_attempts: u32,
+    content_format: ContentFormat,
+) -> Result<(), Box<dyn Error>> {
+    let html = fetch_text(
+        ELITE_DANGEROUS_COMMUNITY_SITE,
+        retry_max_attempts,
+        retry_base_delay,
+    )
+    .await?;
+
+    let mut failed_pages = list_failed_pages()?;
+    println!("Previously failed pages to resume: {}", failed_pages.len());
     let mut downloaded_pages = list_downloaded_pages()?;
     println!(
         "Downloaded pages before starting: {}",
@@ -245,21 +344,36 @@ pub async fn extract_all_pages(sequentially: bool) -> Result<(), Box<dyn Error>>
     let links = extracted_links
         .difference(&downloaded_pages)
         .cloned()
+        .chain(failed_pages.keys().cloned())
         .collect::<HashSet<String>>();
     println!("Total number of links to extract: {}", links.len());
 
     fs::create_dir_all(EXTRACTED_FILES_LOCATION.clone())?;
 
-    let mut page_extractions;
+    let semaphore = Arc::new(Semaphore::new(workers.max(1)));
+    // Collect eagerly: `tokio::spawn` only starts a task once it's created, and a lazy iterator
+    // would spawn (and thus start racing for permits) one task at a time as the loop below polls it.
+    let tasks: Vec<_> = links
+        .into_iter()
+        .map(|link| {
+            let semaphore = Arc::clone(&semaphore);
+            tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore should not be closed");
+                extract_page_to_file(&link, retry_max_attempts, retry_base_delay, content_format)
+                    .await
+            })
+        })
+        .collect();
 
-    if sequentially {
-        page_extractions = vec![];
-        for link in links {
-            page_extractions.push(extract_page_to_file(&link).await);
+    let mut page_extractions = Vec::new();
+    for task in tasks {
+        match task.await {
+            Ok(page_extraction) => page_extractions.push(page_extraction),
+            Err(e) => eprintln!("Page extraction task panicked: {}", e),
         }
-    } else {
-        let future_pages = links.iter().map(|link| extract_page(link));
-        page_extractions = join_all(future_pages).await;
     }
 
     page_extractions.iter_mut().for_each(|page_extraction| {
@@ -300,11 +414,3 @@ pub async fn extract_all_pages(sequentially: bool) -> Result<(), Box<dyn Error>>
 
     Ok(())
 }
-
-fn revert_galnet_date(date: &str) -> String {
-    if let Some(cap) = ARTICLE_DATE_MATCHER.captures(date) {
-        format!("{} {} {}", &cap[3], &cap[2], &cap[1])
-    } else {
-        date.to_owned()
-    }
-}
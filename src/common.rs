@@ -1,8 +1,10 @@
+use regex::Regex;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::HashSet,
     error::Error,
     fmt::{self, Debug, Display, Formatter},
+    fs,
     fs::OpenOptions,
     hash::{Hash, Hasher},
 };
@@ -14,8 +16,16 @@ lazy_static! {
     pub(crate) static ref DOWNLOADED_PAGES_FILE: String = String::from(EXTRACT_LOCATION) + "/successful-pages.json";
     pub(crate) static ref FAILED_PAGES_FILE: String = String::from(EXTRACT_LOCATION) + "/failed-pages.json";
     pub(crate) static ref EXTRACTED_FILES_LOCATION: String = String::from(EXTRACT_LOCATION) + "/files";
+
+    // MATCHERS
+    pub(crate) static ref ARTICLE_DATE_MATCHER: Regex =
+        Regex::new(r"(\d{2})[\s-](\w{3})[\s-](\d{4,})").expect("Article date matcher");
 }
 
+const GALNET_MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
 #[derive(Debug, Default, Serialize, Deserialize, Eq)]
 pub(crate) struct Article {
     pub(crate) uid: String,
@@ -48,6 +58,42 @@ impl PartialEq for Article {
     }
 }
 
+/// How an article's paragraphs are joined into `Article.content`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContentFormat {
+    /// Paragraphs joined with a single line break, matching the historical single-block body.
+    PlainText,
+    /// Paragraphs separated by a blank line, so the result is valid Markdown prose.
+    Markdown,
+}
+
+impl Default for ContentFormat {
+    fn default() -> Self {
+        ContentFormat::PlainText
+    }
+}
+
+/// Joins already-trimmed, non-empty paragraphs into one body per `format`.
+pub(crate) fn join_paragraphs(paragraphs: &[String], format: ContentFormat) -> String {
+    let separator = match format {
+        ContentFormat::PlainText => "\n",
+        ContentFormat::Markdown => "\n\n",
+    };
+    paragraphs.join(separator)
+}
+
+/// Splits an `Article.content` body into its non-empty, trimmed lines, regardless of whether
+/// it was joined as `ContentFormat::PlainText` (single `\n`) or `ContentFormat::Markdown`
+/// (blank-line `\n\n`) — both collapse to the same non-empty lines once split on `\n`.
+/// A paragraph whose lines were separated by `<br>` comes back as several entries.
+pub(crate) fn content_paragraphs(content: &str) -> Vec<&str> {
+    content
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
 #[derive(Default, Debug, Eq)]
 pub(crate) struct GalnetDate {
     pub(crate) day: String,
@@ -67,6 +113,44 @@ impl PartialEq for GalnetDate {
     }
 }
 
+/// Parses a GalNet date string (e.g. "16 JAN 3301") via [`ARTICLE_DATE_MATCHER`].
+pub(crate) fn parse_galnet_date(date: &str) -> Option<GalnetDate> {
+    ARTICLE_DATE_MATCHER.captures(date).map(|cap| GalnetDate {
+        day: cap[1].to_owned(),
+        month: cap[2].to_owned(),
+        year: cap[3].to_owned(),
+    })
+}
+
+/// Reformats a GalNet date string as "year month day", used for article filenames.
+/// The month stays a name (e.g. "JAN"), so these strings do not sort chronologically.
+pub(crate) fn revert_galnet_date(date: &str) -> String {
+    match parse_galnet_date(date) {
+        Some(d) => format!("{} {} {}", d.year, d.month, d.day),
+        None => date.to_owned(),
+    }
+}
+
+/// Chronological sort key `(year, month, day)` for a GalNet date string, oldest-first.
+/// Unparseable dates sort first.
+pub(crate) fn galnet_date_sort_key(date: &str) -> (i32, u32, u32) {
+    match parse_galnet_date(date) {
+        Some(d) => {
+            let month = GALNET_MONTHS
+                .iter()
+                .position(|m| m.eq_ignore_ascii_case(&d.month))
+                .map(|i| i as u32 + 1)
+                .unwrap_or(0);
+            (
+                d.year.parse().unwrap_or(0),
+                month,
+                d.day.parse().unwrap_or(0),
+            )
+        }
+        None => (0, 0, 0),
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum GalnetError {
     FileError {
@@ -126,6 +210,30 @@ where
     }
 }
 
+/// Escapes `&`, `<`, `>` and `"` so article text can be safely spliced into HTML/XHTML markup.
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 pub(crate) fn list_downloaded_pages() -> Result<HashSet<String>, Box<dyn Error>> {
     Ok(deserialize_from_file(&DOWNLOADED_PAGES_FILE)?.unwrap_or_default())
 }
+
+/// Reads every non-deprecated article JSON file under `EXTRACTED_FILES_LOCATION`.
+pub(crate) fn list_extracted_articles() -> Result<Vec<Article>, Box<dyn Error>> {
+    let mut articles = vec![];
+    for entry in fs::read_dir(EXTRACTED_FILES_LOCATION.clone())? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let article: Article = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        if !article.deprecated {
+            articles.push(article);
+        }
+    }
+    Ok(articles)
+}
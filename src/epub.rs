@@ -0,0 +1,76 @@
+use crate::common::{
+    content_paragraphs, escape_html, galnet_date_sort_key, list_extracted_articles, Article,
+};
+
+use epub_builder::{EpubBuilder, EpubContent, ReferenceType, ZipLibrary};
+use std::{collections::BTreeMap, error::Error, fs::File};
+
+const EPUB_FILE: &str = "./galnet/galnet.epub";
+const EPUB_TITLE: &str = "GalNet News Archive";
+const EPUB_AUTHOR: &str = "Frontier Developments";
+
+fn article_chapter_html(article: &Article) -> String {
+    let content_html = content_paragraphs(&article.content)
+        .iter()
+        .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph)))
+        .collect::<Vec<_>>()
+        .join("");
+
+    format!(
+        "<h1>{title}</h1><p class=\"date\">{date}</p>{content}",
+        title = escape_html(&article.title),
+        date = escape_html(&article.date),
+        content = content_html
+    )
+}
+
+fn build_epub(articles: &[Article], title: &str, output_path: &str) -> Result<(), Box<dyn Error>> {
+    let mut output = File::create(output_path)?;
+    let mut builder = EpubBuilder::new(ZipLibrary::new()?)?;
+    builder.metadata("title", title)?.metadata("author", EPUB_AUTHOR)?;
+    builder.inline_toc();
+
+    for (index, article) in articles.iter().enumerate() {
+        builder.add_content(
+            EpubContent::new(
+                format!("chapter_{}.xhtml", index),
+                article_chapter_html(article).as_bytes(),
+            )
+            .title(&article.title)
+            .reftype(ReferenceType::Text),
+        )?;
+    }
+
+    builder.generate(&mut output)?;
+    Ok(())
+}
+
+/// Bundles every extracted article into a single EPUB, ordered chronologically, at
+/// `EPUB_FILE`. When `split_by_year` is set, writes one EPUB per in-game year instead, named
+/// `./galnet/galnet-<year>.epub`.
+pub fn generate_epub(split_by_year: bool) -> Result<(), Box<dyn Error>> {
+    let mut articles = list_extracted_articles()?;
+    articles.sort_by_key(|article| galnet_date_sort_key(&article.date));
+
+    if !split_by_year {
+        let article_count = articles.len();
+        build_epub(&articles, EPUB_TITLE, EPUB_FILE)?;
+        println!("Wrote {} articles to {}", article_count, EPUB_FILE);
+        return Ok(());
+    }
+
+    let mut articles_by_year: BTreeMap<i32, Vec<Article>> = BTreeMap::new();
+    for article in articles {
+        let (year, _, _) = galnet_date_sort_key(&article.date);
+        articles_by_year.entry(year).or_default().push(article);
+    }
+
+    for (year, year_articles) in &articles_by_year {
+        let output_path = format!("./galnet/galnet-{}.epub", year);
+        let title = format!("{} - {}", EPUB_TITLE, year);
+        build_epub(year_articles, &title, &output_path)?;
+        println!("Wrote {} articles to {}", year_articles.len(), output_path);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::common::{galnet_date_sort_key, list_extracted_articles, Article};
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use std::{cmp::Reverse, error::Error, fs, io::Cursor};
+
+const FEED_FILE: &str = "./galnet/feed.xml";
+const FEED_TITLE: &str = "GalNet News";
+const FEED_SELF_URL: &str = "https://community.elitedangerous.com/galnet";
+const FEED_AUTHOR: &str = "Frontier Developments";
+
+fn write_text_element(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    name: &str,
+    text: &str,
+) -> Result<(), Box<dyn Error>> {
+    writer.write_event(Event::Start(BytesStart::new(name)))?;
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+    writer.write_event(Event::End(BytesEnd::new(name)))?;
+    Ok(())
+}
+
+fn write_entry(writer: &mut Writer<Cursor<Vec<u8>>>, article: &Article) -> Result<(), Box<dyn Error>> {
+    writer.write_event(Event::Start(BytesStart::new("entry")))?;
+    write_text_element(writer, "title", &article.title)?;
+    write_text_element(writer, "id", &article.url)?;
+
+    let mut link = BytesStart::new("link");
+    link.push_attribute(("href", article.url.as_str()));
+    writer.write_event(Event::Empty(link))?;
+
+    write_text_element(writer, "updated", &article.extraction_date)?;
+
+    let mut content = BytesStart::new("content");
+    content.push_attribute(("type", "text"));
+    writer.write_event(Event::Start(content))?;
+    writer.write_event(Event::Text(BytesText::new(&article.content)))?;
+    writer.write_event(Event::End(BytesEnd::new("content")))?;
+
+    writer.write_event(Event::End(BytesEnd::new("entry")))?;
+    Ok(())
+}
+
+/// Reads every extracted article under `EXTRACTED_FILES_LOCATION` and writes an Atom feed,
+/// newest article first, to `./galnet/feed.xml`.
+pub fn generate_feed() -> Result<(), Box<dyn Error>> {
+    let mut articles = list_extracted_articles()?;
+    articles.sort_by_key(|article| Reverse(galnet_date_sort_key(&article.date)));
+
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut feed = BytesStart::new("feed");
+    feed.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
+    writer.write_event(Event::Start(feed))?;
+
+    write_text_element(&mut writer, "title", FEED_TITLE)?;
+    write_text_element(&mut writer, "id", FEED_SELF_URL)?;
+
+    // RFC 4287 requires an author on the feed or on every entry; set it here so entries don't
+    // each need to repeat it.
+    writer.write_event(Event::Start(BytesStart::new("author")))?;
+    write_text_element(&mut writer, "name", FEED_AUTHOR)?;
+    writer.write_event(Event::End(BytesEnd::new("author")))?;
+
+    if let Some(latest) = articles.first() {
+        write_text_element(&mut writer, "updated", &latest.extraction_date)?;
+    }
+
+    for article in &articles {
+        write_entry(&mut writer, article)?;
+    }
+
+    writer.write_event(Event::End(BytesEnd::new("feed")))?;
+
+    fs::write(FEED_FILE, writer.into_inner().into_inner())?;
+    println!("Wrote {} articles to {}", articles.len(), FEED_FILE);
+    Ok(())
+}
@@ -2,14 +2,48 @@
 extern crate lazy_static;
 
 use std::error::Error;
+use std::time::Duration;
 
 mod common;
 // mod cms_scraper;
 mod cmtypage_scraper;
+mod epub;
+mod feed;
+mod site;
 
 use cmtypage_scraper::extract_all_pages;
+use common::ContentFormat;
+
+/// Number of page fetches allowed to run concurrently.
+const DOWNLOAD_WORKERS: usize = 8;
+/// Starting delay for the exponential backoff applied to retried fetches.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Maximum number of attempts per page fetch before it's recorded as failed.
+const RETRY_MAX_ATTEMPTS: u32 = 4;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    extract_all_pages(true).await
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("feed") => feed::generate_feed(),
+        Some("epub") => {
+            let split_by_year = args.get(2).map(String::as_str) == Some("--by-year");
+            epub::generate_epub(split_by_year)
+        }
+        Some("site") => site::generate_site(),
+        _ => {
+            let content_format = if args.get(1).map(String::as_str) == Some("--markdown") {
+                ContentFormat::Markdown
+            } else {
+                ContentFormat::PlainText
+            };
+            extract_all_pages(
+                DOWNLOAD_WORKERS,
+                RETRY_BASE_DELAY,
+                RETRY_MAX_ATTEMPTS,
+                content_format,
+            )
+            .await
+        }
+    }
 }
@@ -0,0 +1,166 @@
+use crate::common::{
+    content_paragraphs, escape_html, galnet_date_sort_key, list_extracted_articles, Article,
+};
+
+use std::{cmp::Reverse, collections::BTreeMap, error::Error, fs, path::Path};
+
+const SITE_LOCATION: &str = "./galnet/site";
+const PAGE_SIZE: usize = 20;
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head><meta charset=\"utf-8\"><title>{title}</title></head>\n<body>\n{body}\n</body>\n</html>\n",
+        title = escape_html(title),
+        body = body
+    )
+}
+
+/// Filename of index page `page_number` (`index.html` for page 1, `page-N.html` otherwise).
+fn index_page_filename(page_number: usize) -> String {
+    if page_number == 1 {
+        "index.html".to_owned()
+    } else {
+        format!("page-{}.html", page_number)
+    }
+}
+
+/// Link to an article's detail page, site-root-relative so it resolves regardless of the
+/// linking page's depth under `SITE_LOCATION` (index/pagination pages are at the root,
+/// taxonomy pages are one level down).
+fn article_link(article: &Article) -> String {
+    format!(
+        "<a href=\"/articles/{uid}.html\">{title}</a>",
+        uid = article.uid,
+        title = escape_html(&article.title)
+    )
+}
+
+fn render_article_list(articles: &[&Article]) -> String {
+    let items = articles
+        .iter()
+        .map(|article| {
+            format!(
+                "<li>{} — {}</li>",
+                escape_html(&article.date),
+                article_link(article)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("<ul>\n{}\n</ul>", items)
+}
+
+fn write_page(path: &str, html: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, html)?;
+    Ok(())
+}
+
+fn render_index_pages(articles: &[Article]) -> Result<(), Box<dyn Error>> {
+    let pages = articles.chunks(PAGE_SIZE.max(1)).collect::<Vec<_>>();
+    let page_count = pages.len().max(1);
+
+    for (index, page_articles) in pages.iter().enumerate() {
+        let page_number = index + 1;
+        let refs = page_articles.iter().collect::<Vec<_>>();
+
+        let mut nav = String::new();
+        if page_number > 1 {
+            nav += &format!(
+                "<a href=\"{}\">Previous</a> ",
+                index_page_filename(page_number - 1)
+            );
+        }
+        if page_number < page_count {
+            nav += &format!(
+                "<a href=\"{}\">Next</a>",
+                index_page_filename(page_number + 1)
+            );
+        }
+
+        let body = format!(
+            "<h1>GalNet News</h1>\n{}\n<nav>{}</nav>",
+            render_article_list(&refs),
+            nav
+        );
+
+        let filename = format!("{}/{}", SITE_LOCATION, index_page_filename(page_number));
+        write_page(&filename, &html_page("GalNet News", &body))?;
+    }
+    Ok(())
+}
+
+fn render_taxonomy_pages(articles: &[Article]) -> Result<(), Box<dyn Error>> {
+    let mut by_year: BTreeMap<i32, Vec<&Article>> = BTreeMap::new();
+    let mut by_month: BTreeMap<(i32, u32), Vec<&Article>> = BTreeMap::new();
+
+    for article in articles {
+        let (year, month, _) = galnet_date_sort_key(&article.date);
+        by_year.entry(year).or_default().push(article);
+        by_month.entry((year, month)).or_default().push(article);
+    }
+
+    for (year, year_articles) in &by_year {
+        let body = format!("<h1>{}</h1>\n{}", year, render_article_list(year_articles));
+        write_page(
+            &format!("{}/year/{}.html", SITE_LOCATION, year),
+            &html_page(&year.to_string(), &body),
+        )?;
+    }
+
+    for ((year, month), month_articles) in &by_month {
+        let label = format!("{:04}-{:02}", year, month);
+        let body = format!("<h1>{}</h1>\n{}", label, render_article_list(month_articles));
+        write_page(
+            &format!("{}/month/{}.html", SITE_LOCATION, label),
+            &html_page(&label, &body),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn render_detail_pages(articles: &[Article]) -> Result<(), Box<dyn Error>> {
+    for article in articles {
+        let content_html = content_paragraphs(&article.content)
+            .iter()
+            .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let body = format!(
+            "<h1>{}</h1>\n<p class=\"date\">{}</p>\n{}\n<p><a href=\"{}\">Original article</a></p>",
+            escape_html(&article.title),
+            escape_html(&article.date),
+            content_html,
+            escape_html(&article.url)
+        );
+        write_page(
+            &format!("{}/articles/{}.html", SITE_LOCATION, article.uid),
+            &html_page(&article.title, &body),
+        )?;
+    }
+    Ok(())
+}
+
+/// Renders every extracted article into a paginated static HTML site under `SITE_LOCATION`,
+/// newest article first: an index paginated by `PAGE_SIZE`, year/month taxonomy pages, and
+/// one detail page per `Article.uid`.
+pub fn generate_site() -> Result<(), Box<dyn Error>> {
+    let mut articles = list_extracted_articles()?;
+    articles.sort_by_key(|article| Reverse(galnet_date_sort_key(&article.date)));
+
+    fs::create_dir_all(SITE_LOCATION)?;
+    render_index_pages(&articles)?;
+    render_taxonomy_pages(&articles)?;
+    render_detail_pages(&articles)?;
+
+    println!(
+        "Wrote site for {} articles to {}",
+        articles.len(),
+        SITE_LOCATION
+    );
+    Ok(())
+}